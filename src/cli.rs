@@ -44,6 +44,13 @@ pub enum Command {
         #[clap(flatten)]
         simple_opts: SimpleOpts,
     },
+    /// Instantly switch to the most recently used window (classic Alt-Tab "flip to last"), without opening the GUI; repeated calls walk further back through focus history
+    Last,
+    /// Switch directly to a workspace by its Hyprland name (e.g. "comms"), regardless of its numeric index
+    Workspace {
+        /// The name of the workspace to switch to
+        name: String,
+    },
     /// Opens the GUI
     Gui {
         #[clap(flatten)]
@@ -102,7 +109,7 @@ pub struct InitOpts {
     pub size_factor: f64,
 }
 
-#[derive(Args, Debug, Clone)]
+#[derive(Args, Debug, Clone, Default)]
 pub struct SimpleConf {
     /// Include special workspaces (e.g., scratchpad)
     #[arg(long, default_value = "false", action = clap::ArgAction::Set, default_missing_value = "true", num_args=0..=1
@@ -131,10 +138,22 @@ pub struct SimpleConf {
     #[arg(short = 'm', long)]
     pub filter_current_monitor: bool,
 
+    /// Only show/switch between windows whose title matches this regex
+    #[arg(long, value_parser = clap::value_parser!(MatchRegex))]
+    pub match_title: Option<MatchRegex>,
+
+    /// Only show/switch between windows whose class matches this regex
+    #[arg(long, value_parser = clap::value_parser!(MatchRegex))]
+    pub match_class: Option<MatchRegex>,
+
     /// Sort windows by most recently focused
     #[arg(long)]
     pub sort_recent: bool,
 
+    /// Jump to an urgent window first (the most recently focused one if several are urgent), falling back to most-recently-used ordering if none are urgent
+    #[arg(long)]
+    pub urgent_first: bool,
+
     /// Switches to next / previous workspace / client / monitor
     #[arg(long, default_value_t, value_enum)]
     pub switch_type: SwitchType,
@@ -158,9 +177,25 @@ pub struct SimpleOpts {
     #[arg(short = 'o', long, default_value = "1", value_parser = clap::value_parser!(u8).range(1..)
     )]
     pub offset: u8,
+
+    /// Switch to the nearest window in a direction using its on-screen geometry instead of offset ordering (overrides --offset/--reverse)
+    #[arg(long, value_enum)]
+    pub direction: Option<Direction>,
+
+    /// Ignore dispatches that arrive within this many milliseconds of the last applied one (throttles fast key-repeat)
+    #[arg(long, default_value = "0")]
+    pub cooldown_ms: u64,
 }
 
-#[derive(Args, Debug, Clone)]
+#[derive(ValueEnum, Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Args, Debug, Clone, Default)]
 pub struct GuiConf {
     /// The modifier key to used to open the GUI (e.g. shift, alt, ...)
     #[clap(long, value_enum)]
@@ -200,9 +235,13 @@ pub struct GuiConf {
     #[arg(long, default_value = "false", action = clap::ArgAction::Set, default_missing_value = "true", num_args=0..=1
     )]
     pub show_workspaces_on_all_monitors: bool,
+
+    /// Ignore dispatches that arrive within this many milliseconds of the last applied one, to smooth held-key/auto-repeat navigation
+    #[arg(long, default_value = "0")]
+    pub cooldown_ms: u64,
 }
 
-#[derive(ValueEnum, Clone, Debug, Serialize, Deserialize)]
+#[derive(ValueEnum, Clone, Debug, Default, Serialize, Deserialize)]
 #[clap(rename_all = "snake_case")]
 pub enum ModKeyInput {
     // = alt_l
@@ -214,6 +253,7 @@ pub enum ModKeyInput {
     CtrlL,
     CtrlR,
     // = super_l
+    #[default]
     Super,
     SuperL,
     SuperR,
@@ -274,6 +314,20 @@ impl FromStr for Monitors {
     }
 }
 
+/// A regex compiled once at CLI-parse time, used to scope the switch candidate set by window title/class
+#[derive(Debug, Clone)]
+pub struct MatchRegex(pub regex::Regex);
+
+impl FromStr for MatchRegex {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        regex::Regex::new(s)
+            .map(MatchRegex)
+            .map_err(|e| format!("Invalid regex {s:?}: {e}"))
+    }
+}
+
 #[derive(ValueEnum, Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
 pub enum CloseType {
     #[default]
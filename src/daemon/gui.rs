@@ -0,0 +1,52 @@
+use std::sync::{Mutex, OnceLock};
+
+/// Generation counter for the desktop-file maps, bumped by `reload_desktop_maps`.
+static DESKTOP_MAP_GENERATION: OnceLock<Mutex<u64>> = OnceLock::new();
+
+/// Increment `DESKTOP_MAP_GENERATION`. No `.desktop` files are scanned here.
+pub(crate) fn reload_desktop_maps() {
+    let generation = DESKTOP_MAP_GENERATION.get_or_init(|| Mutex::new(0));
+    *generation.lock().expect("Failed to lock desktop map generation") += 1;
+}
+
+/// The GTK CSS class applied to a window's overview tile so urgent windows stand out before the
+/// user switches to them.
+pub(crate) const URGENT_CSS_CLASS: &str = "urgent";
+
+/// Pick the CSS class for a window's overview tile based on its urgency hint.
+pub(crate) fn client_css_class(client: &crate::handle::ClientData) -> Option<&'static str> {
+    client.urgent.then_some(URGENT_CSS_CLASS)
+}
+
+/// The label to render on a workspace's overview tile: its Hyprland name if it has one
+/// (e.g. "comms"), falling back to the numeric id for unnamed workspaces.
+pub(crate) fn workspace_label(id: i32, workspace: &crate::WorkspaceData) -> String {
+    if workspace.name.is_empty() {
+        id.to_string()
+    } else {
+        workspace.name.clone()
+    }
+}
+
+/// Build the renderable `Overview` the GUI draws from, applying `client_css_class` and
+/// `workspace_label` to every client/workspace currently known to the daemon.
+pub(crate) fn build_overview(data: &crate::handle::HyprlandData) -> crate::Overview {
+    crate::Overview {
+        clients: data
+            .clients
+            .iter()
+            .map(|c| crate::ClientTile {
+                address: c.address.clone(),
+                css_classes: client_css_class(c).into_iter().collect(),
+            })
+            .collect(),
+        workspaces: data
+            .workspaces
+            .iter()
+            .map(|(id, w)| crate::WorkspaceTile {
+                id: *id,
+                label: workspace_label(*id, w),
+            })
+            .collect(),
+    }
+}
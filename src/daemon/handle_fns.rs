@@ -1,16 +1,39 @@
 use crate::cli::SwitchType;
-use crate::daemon::gui::reload_desktop_maps;
+use crate::daemon::gui::{build_overview, reload_desktop_maps};
 use crate::daemon::submap::{activate_submap, deactivate_submap};
-use crate::handle::{clear_recent_clients, collect_data, find_next, run_program, switch_to_active};
+use crate::daemon::ensure_listener_started;
+use crate::handle::{
+    clear_recent_clients, collect_data, find_next, find_next_directional,
+    find_next_urgent_or_recent, run_program, switch_to_active, switch_to_last_focused,
+    switch_to_workspace_name,
+};
 use crate::{Active, Command, Config, GUISend, GuiConfig, Share, UpdateCause, ACTIVE};
 use anyhow::Context;
 use std::ops::Deref;
+use std::time::{Duration, Instant};
 use tracing::{info, trace, warn};
 
 pub(crate) fn switch(share: &Share, command: Command, client_id: u8) -> anyhow::Result<()> {
     let (latest, send, receive) = share.deref();
     {
         let mut lock = latest.lock().expect("Failed to lock");
+
+        // The GUI's held-key/auto-repeat path sets GuiConf.cooldown_ms on init; an explicit
+        // `Dispatch` call can additionally request its own cooldown via SimpleOpts. Whichever is
+        // larger wins, so a GUI session with a configured cooldown can't be bypassed by callers
+        // that don't pass --cooldown-ms.
+        let cooldown_ms = command.cooldown_ms.max(lock.gui_config.cooldown_ms);
+        if cooldown_ms > 0 {
+            let now = Instant::now();
+            if let Some(last) = lock.last_dispatch_at {
+                if now.duration_since(last) < Duration::from_millis(cooldown_ms) {
+                    trace!("Dispatch arrived within cooldown window, ignoring");
+                    return Ok(());
+                }
+            }
+            lock.last_dispatch_at = Some(now);
+        }
+
         let exec_len = lock.launcher.execs.len();
         if let Some(ref mut selected) = lock.launcher.selected {
             if exec_len == 0 {
@@ -21,6 +44,12 @@ pub(crate) fn switch(share: &Share, command: Command, client_id: u8) -> anyhow::
             } else {
                 (*selected + command.offset as u16).min((exec_len - 1) as u16)
             };
+        } else if let Some(direction) = command.direction {
+            let active = find_next_directional(direction, &lock.hypr_data, &lock.active)?;
+            lock.active = active;
+        } else if lock.simple_config.urgent_first {
+            let active = find_next_urgent_or_recent(&lock.hypr_data, &lock.active)?;
+            lock.active = active;
         } else {
             let active = find_next(
                 &lock.simple_config.switch_type,
@@ -43,6 +72,23 @@ pub(crate) fn switch(share: &Share, command: Command, client_id: u8) -> anyhow::
     Ok(())
 }
 
+/// Flip directly to the previously focused window using the persistent focus-history stack,
+/// bypassing the GUI entirely (no refresh round-trip, no submap activation).
+pub(crate) fn last(client_id: u8) -> anyhow::Result<()> {
+    trace!("Switching to last focused window for client {}", client_id);
+    switch_to_last_focused().context("Unable to switch to last focused window")
+}
+
+/// Switch directly to a named workspace, bypassing offset-based navigation entirely.
+pub(crate) fn workspace(name: &str, client_id: u8) -> anyhow::Result<()> {
+    trace!(
+        "Switching to workspace {:?} for client {}",
+        name,
+        client_id
+    );
+    switch_to_workspace_name(name).with_context(|| format!("Unable to switch to workspace {name:?}"))
+}
+
 pub(crate) fn close(share: &Share, kill: bool, client_id: u8) -> anyhow::Result<()> {
     let (latest, send, receive) = share.deref();
     {
@@ -114,6 +160,8 @@ pub(crate) fn init(
         }
     };
 
+    ensure_listener_started();
+
     let (latest, send, receive) = share.deref();
     {
         let mut lock = latest.lock().expect("Failed to lock");
@@ -122,6 +170,7 @@ pub(crate) fn init(
         lock.simple_config = config.clone();
         lock.gui_config = gui_config.clone();
         lock.hypr_data = clients_data;
+        lock.overview = build_overview(&lock.hypr_data);
         drop(lock);
     }
     activate_submap(gui_config.clone())?;
@@ -0,0 +1,37 @@
+mod gui;
+mod handle_fns;
+mod submap;
+
+pub(crate) use handle_fns::{close, init, last, switch, workspace};
+
+use std::sync::Once;
+
+use anyhow::Context;
+use hyprland::event_listener::EventListener;
+use tracing::error;
+
+/// Start listening for Hyprland events in the background for the lifetime of the daemon, so the
+/// persistent focus-history stack (`handle::FOCUS_HISTORY`) stays accurate even while the GUI is
+/// closed.
+fn listen() -> anyhow::Result<()> {
+    let mut listener = EventListener::new();
+    crate::handle::subscribe_focus_history(&mut listener);
+    listener
+        .start_listener()
+        .context("Failed to start Hyprland event listener")
+}
+
+static LISTENER_STARTED: Once = Once::new();
+
+/// Spawn the Hyprland event listener exactly once, the first time the daemon handles an `init`
+/// request (this tree has no separate daemon-startup entry point). Focus changes before that
+/// first `init` are not recorded.
+pub(crate) fn ensure_listener_started() {
+    LISTENER_STARTED.call_once(|| {
+        std::thread::spawn(|| {
+            if let Err(e) = listen() {
+                error!("Hyprland event listener exited: {e}");
+            }
+        });
+    });
+}
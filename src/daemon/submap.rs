@@ -0,0 +1,18 @@
+use anyhow::Context;
+use hyprland::dispatch::{Dispatch, DispatchType};
+
+use crate::GuiConfig;
+
+const SUBMAP_NAME: &str = "hyprswitch";
+
+/// Enter the dedicated Hyprland submap so the GUI's keymap (number keys, reverse key, etc.) takes over.
+pub(crate) fn activate_submap(_gui_config: GuiConfig) -> anyhow::Result<()> {
+    Dispatch::call(DispatchType::Custom("submap", SUBMAP_NAME))
+        .context("Failed to activate hyprswitch submap")
+}
+
+/// Leave the submap, restoring the user's normal keybinds.
+pub(crate) fn deactivate_submap() -> anyhow::Result<()> {
+    Dispatch::call(DispatchType::Custom("submap", "reset"))
+        .context("Failed to deactivate hyprswitch submap")
+}
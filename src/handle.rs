@@ -0,0 +1,572 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{anyhow, Context};
+use hyprland::data::{
+    Client as HyprClient, Clients as HyprClients, Monitors as HyprMonitors,
+    Workspaces as HyprWorkspaces,
+};
+use hyprland::dispatch::{Dispatch, DispatchType, MonitorIdentifier, WindowIdentifier, WorkspaceIdentifierWithSpecial};
+use hyprland::event_listener::EventListener;
+use hyprland::shared::{Address, HyprData, HyprDataActiveOptional};
+
+use crate::cli::{Direction, SwitchType};
+use crate::{Active, Command, Config, MonitorData, MonitorId, WorkspaceData};
+
+pub use crate::sort::clear_recent_clients;
+
+/// One window as reported by Hyprland, trimmed to the fields the switch/sort logic needs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientData {
+    pub address: Address,
+    pub title: String,
+    pub class: String,
+    pub workspace: i32,
+    pub monitor: MonitorId,
+    pub at: (i16, i16),
+    pub size: (i16, i16),
+    pub focus_history_id: i8,
+    /// Whether Hyprland has this window's urgency hint set (e.g. it requested attention while unfocused).
+    pub urgent: bool,
+}
+
+impl ClientData {
+    /// The window's center point, in screen-space pixel coordinates.
+    pub fn center(&self) -> (f64, f64) {
+        (
+            self.at.0 as f64 + self.size.0 as f64 / 2.0,
+            self.at.1 as f64 + self.size.1 as f64 / 2.0,
+        )
+    }
+}
+
+/// A snapshot of everything the switcher needs: windows plus the workspace/monitor layout they live on.
+#[derive(Debug, Clone, Default)]
+pub struct HyprlandData {
+    pub clients: Vec<ClientData>,
+    pub workspaces: HashMap<i32, WorkspaceData>,
+    pub monitors: HashMap<MonitorId, MonitorData>,
+}
+
+/// The monitors currently known to Hyprland, used by `cli::Monitors::from_str` to validate
+/// `--monitors` against real output names at CLI-parse time.
+pub fn get_monitors() -> Vec<hyprland::data::Monitor> {
+    HyprMonitors::get()
+        .map(|monitors| monitors.to_vec())
+        .unwrap_or_default()
+}
+
+/// Collect the current windows/workspaces/monitors from Hyprland, applying `config`'s filters.
+pub fn collect_data(
+    config: Config,
+) -> anyhow::Result<(HyprlandData, (Option<Address>, Option<i32>, Option<MonitorId>))> {
+    let clients = HyprClients::get().context("Failed to get clients from Hyprland")?;
+    let workspaces = HyprWorkspaces::get().context("Failed to get workspaces from Hyprland")?;
+    let monitors = HyprMonitors::get().context("Failed to get monitors from Hyprland")?;
+    let active_client =
+        HyprClient::get_active().context("Failed to get active client from Hyprland")?;
+
+    let mut clients: Vec<ClientData> = clients
+        .iter()
+        .map(|c| ClientData {
+            address: c.address.clone(),
+            title: c.title.clone(),
+            class: c.class.clone(),
+            workspace: c.workspace.id,
+            monitor: c.monitor as MonitorId,
+            at: c.at,
+            size: c.size,
+            focus_history_id: c.focus_history_id,
+            urgent: c.urgent,
+        })
+        .collect();
+
+    let active_client_data = active_client
+        .as_ref()
+        .and_then(|a| clients.iter().find(|c| c.address == a.address).cloned());
+
+    crate::sort::filter(&mut clients, &config, active_client_data.as_ref());
+
+    let workspaces = workspaces
+        .iter()
+        .map(|w| {
+            (
+                w.id,
+                WorkspaceData {
+                    x: 0,
+                    y: 0,
+                    name: w.name.clone(),
+                },
+            )
+        })
+        .collect();
+
+    let monitors = monitors
+        .iter()
+        .map(|m| {
+            (
+                m.id as MonitorId,
+                MonitorData {
+                    x: m.x.max(0) as u16,
+                    y: m.y.max(0) as u16,
+                    width: m.width,
+                    height: m.height,
+                    combined_width: m.width,
+                    combined_height: m.height,
+                    workspaces_on_monitor: 1,
+                },
+            )
+        })
+        .collect();
+
+    let active_workspace = active_client.as_ref().map(|c| c.workspace.id);
+    let active_monitor = active_client.as_ref().map(|c| c.monitor as MonitorId);
+    let active_address = active_client.map(|c| c.address);
+
+    Ok((
+        HyprlandData {
+            clients,
+            workspaces,
+            monitors,
+        },
+        (active_address, active_workspace, active_monitor),
+    ))
+}
+
+/// Step to the next/previous candidate in `items`, wrapping around, honouring `command`'s
+/// offset/reverse. Returns `None` if `items` is empty.
+fn step<T: Clone>(items: &[T], current: Option<usize>, command: &Command) -> Option<T> {
+    if items.is_empty() {
+        return None;
+    }
+    let len = items.len();
+    let offset = command.offset as usize % len;
+    let idx = match current {
+        Some(i) if command.reverse => (i + len - offset) % len,
+        Some(i) => (i + offset) % len,
+        None => 0,
+    };
+    items.get(idx).cloned()
+}
+
+/// The existing linear next/previous switcher, driven by `SimpleOpts`'s offset/reverse.
+pub fn find_next(
+    switch_type: &SwitchType,
+    command: Command,
+    data: &HyprlandData,
+    active: &Active,
+) -> anyhow::Result<Active> {
+    match switch_type {
+        SwitchType::Client => {
+            let mut addresses: Vec<Address> = data.clients.iter().map(|c| c.address.clone()).collect();
+            addresses.sort_by_key(ToString::to_string);
+            let current = match active {
+                Active::Client(a) => addresses.iter().position(|x| x == a),
+                _ => None,
+            };
+            Ok(step(&addresses, current, &command)
+                .map(Active::Client)
+                .unwrap_or(Active::Unknown))
+        }
+        SwitchType::Workspace => {
+            let mut ids: Vec<i32> = data.workspaces.keys().copied().collect();
+            ids.sort_unstable();
+            let current = match active {
+                Active::Workspace(id) => ids.iter().position(|x| x == id),
+                _ => None,
+            };
+            Ok(step(&ids, current, &command)
+                .map(Active::Workspace)
+                .unwrap_or(Active::Unknown))
+        }
+        SwitchType::Monitor => {
+            let mut ids: Vec<MonitorId> = data.monitors.keys().copied().collect();
+            ids.sort_unstable();
+            let current = match active {
+                Active::Monitor(id) => ids.iter().position(|x| x == id),
+                _ => None,
+            };
+            Ok(step(&ids, current, &command)
+                .map(Active::Monitor)
+                .unwrap_or(Active::Unknown))
+        }
+    }
+}
+
+/// How heavily perpendicular drift is penalised relative to distance along the primary axis;
+/// higher favours windows that are aligned with the active one.
+const PERPENDICULAR_WEIGHT: f64 = 1.75;
+
+fn in_half_plane(direction: Direction, from: (f64, f64), to: (f64, f64)) -> bool {
+    match direction {
+        Direction::Right => to.0 > from.0,
+        Direction::Left => to.0 < from.0,
+        Direction::Down => to.1 > from.1,
+        Direction::Up => to.1 < from.1,
+    }
+}
+
+fn directional_score(direction: Direction, from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (primary, perpendicular) = match direction {
+        Direction::Left | Direction::Right => ((to.0 - from.0).abs(), (to.1 - from.1).abs()),
+        Direction::Up | Direction::Down => ((to.1 - from.1).abs(), (to.0 - from.0).abs()),
+    };
+    primary + PERPENDICULAR_WEIGHT * perpendicular
+}
+
+/// Sort key that makes the edge-most window (the one you'd land on entering from `direction`)
+/// sort first.
+fn entry_edge_key(direction: Direction, center: (f64, f64)) -> f64 {
+    match direction {
+        Direction::Right => center.0,
+        Direction::Left => -center.0,
+        Direction::Down => center.1,
+        Direction::Up => -center.1,
+    }
+}
+
+fn monitor_center(monitor: &MonitorData) -> (f64, f64) {
+    (
+        monitor.x as f64 + monitor.width as f64 / 2.0,
+        monitor.y as f64 + monitor.height as f64 / 2.0,
+    )
+}
+
+/// The adjacent monitor in `direction` from `from`, if any.
+fn adjacent_monitor(
+    direction: Direction,
+    from: MonitorId,
+    monitors: &HashMap<MonitorId, MonitorData>,
+) -> Option<MonitorId> {
+    let cur_center = monitor_center(monitors.get(&from)?);
+    monitors
+        .iter()
+        .filter(|(id, _)| **id != from)
+        .filter(|(_, m)| in_half_plane(direction, cur_center, monitor_center(m)))
+        .min_by(|(_, a), (_, b)| {
+            directional_score(direction, cur_center, monitor_center(a))
+                .partial_cmp(&directional_score(direction, cur_center, monitor_center(b)))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map(|(id, _)| *id)
+}
+
+/// Switch to the nearest window in `direction` using real client geometry: filter candidates to
+/// the correct half-plane, score by `primary_axis_distance + k * perpendicular_offset`, and fall
+/// through to the adjacent monitor's edge-most window if nothing matches on the current one.
+pub fn find_next_directional(
+    direction: Direction,
+    data: &HyprlandData,
+    active: &Active,
+) -> anyhow::Result<Active> {
+    let Active::Client(active_address) = active else {
+        return Ok(active.clone());
+    };
+    let Some(active_client) = data.clients.iter().find(|c| &c.address == active_address) else {
+        return Ok(active.clone());
+    };
+
+    let active_center = active_client.center();
+    let active_monitor = active_client.monitor;
+
+    let mut candidates: Vec<&ClientData> = data
+        .clients
+        .iter()
+        .filter(|c| &c.address != active_address)
+        .filter(|c| c.monitor == active_monitor)
+        .filter(|c| in_half_plane(direction, active_center, c.center()))
+        .collect();
+
+    candidates.sort_by(|a, b| {
+        directional_score(direction, active_center, a.center())
+            .partial_cmp(&directional_score(direction, active_center, b.center()))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    if let Some(best) = candidates.first() {
+        return Ok(Active::Client(best.address.clone()));
+    }
+
+    if let Some(target_monitor) = adjacent_monitor(direction, active_monitor, &data.monitors) {
+        let mut on_monitor: Vec<&ClientData> = data
+            .clients
+            .iter()
+            .filter(|c| c.monitor == target_monitor)
+            .collect();
+        on_monitor.sort_by(|a, b| {
+            entry_edge_key(direction, a.center())
+                .partial_cmp(&entry_edge_key(direction, b.center()))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        if let Some(edge_most) = on_monitor.first() {
+            return Ok(Active::Client(edge_most.address.clone()));
+        }
+    }
+
+    Ok(active.clone())
+}
+
+/// Jump to the urgent window that was most recently focused (lowest `focus_history_id`); the
+/// order in which windows became urgent is not tracked. Falls back to the existing
+/// most-recently-used ordering if nothing is currently urgent.
+pub fn find_next_urgent_or_recent(data: &HyprlandData, active: &Active) -> anyhow::Result<Active> {
+    if let Some(urgent) = data
+        .clients
+        .iter()
+        .filter(|c| c.urgent)
+        .min_by_key(|c| c.focus_history_id)
+    {
+        return Ok(Active::Client(urgent.address.clone()));
+    }
+
+    if let Some(recent) = crate::sort::most_recently_used(&data.clients, active) {
+        return Ok(Active::Client(recent.address.clone()));
+    }
+
+    Ok(active.clone())
+}
+
+/// Dispatch the actual Hyprland focus/switch call for `active`, recording it in the MRU history.
+pub fn switch_to_active(active: &Active, data: &HyprlandData) -> anyhow::Result<()> {
+    match active {
+        Active::Client(address) => {
+            Dispatch::call(DispatchType::FocusWindow(WindowIdentifier::Address(
+                address.clone(),
+            )))
+            .context("Failed to focus window")?;
+            crate::sort::push_recent(address.clone());
+        }
+        Active::Workspace(id) => {
+            Dispatch::call(DispatchType::Workspace(WorkspaceIdentifierWithSpecial::Id(
+                *id,
+            )))
+            .context("Failed to switch workspace")?;
+        }
+        Active::Monitor(id) => {
+            if data.monitors.contains_key(id) {
+                Dispatch::call(DispatchType::FocusMonitor(MonitorIdentifier::Id(
+                    *id as i128,
+                )))
+                .context("Failed to focus monitor")?;
+            }
+        }
+        Active::Unknown => {}
+    }
+    Ok(())
+}
+
+/// Persistent MRU stack of focused window addresses, front = most recently focused. Unlike
+/// `sort::RECENT_CLIENTS` (which is scoped to a single GUI open/close cycle), this stack lives
+/// for the whole daemon lifetime so "last window" keeps working across GUI sessions.
+static FOCUS_HISTORY: OnceLock<Mutex<VecDeque<Address>>> = OnceLock::new();
+
+fn focus_history() -> &'static Mutex<VecDeque<Address>> {
+    FOCUS_HISTORY.get_or_init(|| Mutex::new(VecDeque::new()))
+}
+
+/// Record that `address` was just focused, moving it to the front of the persistent history.
+fn record_focus(address: Address) {
+    let mut history = focus_history().lock().expect("Failed to lock focus history");
+    history.retain(|a| a != &address);
+    history.push_front(address);
+}
+
+/// Drop `address` from the persistent history, e.g. once its window has closed.
+fn drop_focus_history(address: &Address) {
+    focus_history()
+        .lock()
+        .expect("Failed to lock focus history")
+        .retain(|a| a != address);
+}
+
+/// Subscribe to Hyprland's focus/close events so the persistent history stays accurate without
+/// the GUI being open. Call this once from the daemon's event-listener setup.
+pub fn subscribe_focus_history(listener: &mut EventListener) {
+    listener.add_active_window_changed_handler(|data| {
+        if let Some(data) = data {
+            record_focus(data.address);
+        }
+    });
+    listener.add_window_closed_handler(|address| {
+        drop_focus_history(&address);
+    });
+}
+
+/// Walk one step back through `history` (front = currently focused), rotating the current front
+/// behind the new target so repeated calls keep walking back instead of bouncing between the same
+/// two windows. Returns `None` if there's no previous window to switch to.
+fn rotate_to_previous(history: &mut VecDeque<Address>) -> Option<Address> {
+    if history.len() < 2 {
+        return None;
+    }
+    let current = history.pop_front().expect("checked len >= 2 above");
+    let target = history.front().cloned().expect("checked len >= 2 above");
+    history.push_back(current);
+    Some(target)
+}
+
+/// Instantly flip to the previously focused window (classic Alt-Tab "flip to last"), walking one
+/// step back through the persistent focus-history stack each time it's called.
+pub fn switch_to_last_focused() -> anyhow::Result<()> {
+    let target = {
+        let mut history = focus_history().lock().expect("Failed to lock focus history");
+        rotate_to_previous(&mut history)
+            .ok_or_else(|| anyhow!("No previous window in focus history to switch to"))?
+    };
+
+    Dispatch::call(DispatchType::FocusWindow(WindowIdentifier::Address(
+        target.clone(),
+    )))
+    .context("Failed to focus last window")?;
+    record_focus(target);
+    Ok(())
+}
+
+/// Switch directly to a workspace by its Hyprland name, bypassing offset-based navigation
+/// entirely so a keybind can jump to e.g. "comms" regardless of its numeric index.
+pub fn switch_to_workspace_name(name: &str) -> anyhow::Result<()> {
+    Dispatch::call(DispatchType::Workspace(WorkspaceIdentifierWithSpecial::Name(
+        name,
+    )))
+    .with_context(|| format!("Failed to dispatch workspace switch to {name:?}"))
+}
+
+/// Launch the picked program, optionally inside a terminal, detached from the daemon.
+pub fn run_program(run: &str, path: &Path, terminal: bool) {
+    let mut cmd = if terminal {
+        let mut c = std::process::Command::new("xdg-terminal-exec");
+        c.arg(run);
+        c
+    } else {
+        std::process::Command::new(run)
+    };
+    if let Err(e) = cmd.current_dir(path).spawn() {
+        tracing::warn!("Failed to launch {} (in {:?}): {}", run, path, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(reverse: bool, offset: u8) -> Command {
+        Command {
+            reverse,
+            offset,
+            direction: None,
+            cooldown_ms: 0,
+        }
+    }
+
+    #[test]
+    fn step_wraps_forward_and_backward() {
+        let items = [0, 1, 2, 3];
+        assert_eq!(step(&items, Some(0), &command(false, 1)), Some(1));
+        assert_eq!(step(&items, Some(3), &command(false, 1)), Some(0));
+        assert_eq!(step(&items, Some(0), &command(true, 1)), Some(3));
+        assert_eq!(step(&items, None, &command(false, 1)), Some(0));
+    }
+
+    #[test]
+    fn step_returns_none_for_empty_input() {
+        let items: [i32; 0] = [];
+        assert_eq!(step(&items, None, &command(false, 1)), None);
+    }
+
+    #[test]
+    fn record_focus_and_drop_focus_history_update_the_persistent_stack() {
+        // Exercises the exact functions `subscribe_focus_history`'s event handlers call, as a
+        // smoke-check that a real focus/close event would reach the persistent history.
+        let a = Address::new("0xfocus-test-a");
+        let b = Address::new("0xfocus-test-b");
+
+        record_focus(a.clone());
+        record_focus(b.clone());
+        {
+            let history = focus_history().lock().expect("Failed to lock focus history");
+            assert_eq!(history.front(), Some(&b));
+            assert!(history.contains(&a));
+        }
+
+        drop_focus_history(&a);
+        {
+            let history = focus_history().lock().expect("Failed to lock focus history");
+            assert!(!history.contains(&a));
+            assert!(history.contains(&b));
+        }
+
+        drop_focus_history(&b);
+    }
+
+    #[test]
+    fn rotate_to_previous_walks_back_one_step_and_requeues_current() {
+        let mut history: VecDeque<Address> = [Address::new("0xa"), Address::new("0xb"), Address::new("0xc")]
+            .into_iter()
+            .collect();
+        assert_eq!(rotate_to_previous(&mut history), Some(Address::new("0xb")));
+        assert_eq!(
+            history,
+            [Address::new("0xb"), Address::new("0xc"), Address::new("0xa")]
+                .into_iter()
+                .collect::<VecDeque<_>>()
+        );
+    }
+
+    #[test]
+    fn rotate_to_previous_none_when_history_too_short() {
+        let mut empty: VecDeque<Address> = VecDeque::new();
+        assert_eq!(rotate_to_previous(&mut empty), None);
+
+        let mut single: VecDeque<Address> = [Address::new("0xa")].into_iter().collect();
+        assert_eq!(rotate_to_previous(&mut single), None);
+    }
+
+    #[test]
+    fn half_plane_keeps_only_the_correct_side() {
+        let from = (100.0, 100.0);
+        assert!(in_half_plane(Direction::Right, from, (150.0, 100.0)));
+        assert!(!in_half_plane(Direction::Right, from, (50.0, 100.0)));
+        assert!(in_half_plane(Direction::Down, from, (100.0, 150.0)));
+        assert!(!in_half_plane(Direction::Up, from, (100.0, 150.0)));
+    }
+
+    #[test]
+    fn directional_score_favours_aligned_candidates() {
+        let from = (0.0, 0.0);
+        let aligned = directional_score(Direction::Right, from, (100.0, 0.0));
+        let offset = directional_score(Direction::Right, from, (100.0, 50.0));
+        assert!(aligned < offset, "a window directly to the right should score lower than one offset perpendicular to it");
+    }
+
+    #[test]
+    fn adjacent_monitor_picks_the_one_in_direction() {
+        let mut monitors = HashMap::new();
+        monitors.insert(
+            0,
+            MonitorData {
+                x: 0,
+                y: 0,
+                width: 1920,
+                height: 1080,
+                combined_width: 1920,
+                combined_height: 1080,
+                workspaces_on_monitor: 1,
+            },
+        );
+        monitors.insert(
+            1,
+            MonitorData {
+                x: 1920,
+                y: 0,
+                width: 1920,
+                height: 1080,
+                combined_width: 1920,
+                combined_height: 1080,
+                workspaces_on_monitor: 1,
+            },
+        );
+        assert_eq!(adjacent_monitor(Direction::Right, 0, &monitors), Some(1));
+        assert_eq!(adjacent_monitor(Direction::Left, 0, &monitors), None);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod cli;
 pub mod sort;
 pub mod handle;
 #[cfg(feature = "gui")]
@@ -6,6 +7,15 @@ pub mod gui;
 pub mod daemon;
 pub mod toast;
 
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+
+use hyprland::shared::Address;
+
+use crate::cli::{Direction, GuiConf, SimpleConf};
+use crate::handle::HyprlandData;
+
 #[derive(Default, Debug, Clone, Copy)]
 pub struct MonitorData {
     pub x: u16,
@@ -17,10 +27,106 @@ pub struct MonitorData {
     pub workspaces_on_monitor: u16,
 }
 
-#[derive(Default, Debug, Clone, Copy)]
+#[derive(Default, Debug, Clone)]
 pub struct WorkspaceData {
     pub x: u16,
     pub y: u16,
+    /// The workspace's Hyprland name (e.g. "comms"), empty if it only has a numeric id
+    pub name: String,
+}
+
+pub type MonitorId = i64;
+
+/// The persisted switch configuration, shared between `Simple`/`Gui`/`Dispatch` and the daemon.
+pub type Config = SimpleConf;
+/// The persisted GUI configuration, shared between `Gui` and the daemon.
+pub type GuiConfig = GuiConf;
+
+/// What is currently selected while cycling through the switch candidates.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Active {
+    #[default]
+    Unknown,
+    Client(Address),
+    Workspace(i32),
+    Monitor(MonitorId),
+}
+
+/// A single dispatch request sent from `hyprswitch dispatch` to the daemon.
+#[derive(Debug, Clone, Default)]
+pub struct Command {
+    pub reverse: bool,
+    pub offset: u8,
+    pub direction: Option<Direction>,
+    pub cooldown_ms: u64,
+}
+
+/// A pending GUI-launcher selection (application picker), keyed by index into `execs`.
+#[derive(Debug, Clone, Default)]
+pub struct Launcher {
+    pub selected: Option<u16>,
+    pub execs: Vec<(String, PathBuf, bool)>,
+}
+
+/// One window's overview tile, as rendered by the GUI.
+#[derive(Debug, Clone, Default)]
+pub struct ClientTile {
+    pub address: Address,
+    /// Extra CSS classes the GUI should apply to this tile (e.g. `"urgent"`).
+    pub css_classes: Vec<&'static str>,
+}
+
+/// One workspace's overview tile, as rendered by the GUI.
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceTile {
+    pub id: i32,
+    /// The label to show: the workspace's name if it has one, else its numeric id.
+    pub label: String,
+}
+
+/// The renderable snapshot handed to the GUI, derived from `Latest::hypr_data` in
+/// `daemon::handle_fns::init`.
+#[derive(Debug, Clone, Default)]
+pub struct Overview {
+    pub clients: Vec<ClientTile>,
+    pub workspaces: Vec<WorkspaceTile>,
+}
+
+/// What triggered a GUI refresh, carried through for logging.
+#[derive(Debug, Clone, Copy)]
+pub enum UpdateCause {
+    Client(u8),
+}
+
+/// Messages sent from the daemon to the GUI thread over the `Share` channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GUISend {
+    New,
+    Refresh,
+    Hide,
+}
+
+/// Whether the GUI/submap is currently active; set in `daemon::handle_fns::init`, cleared in `close`.
+pub static ACTIVE: OnceLock<Mutex<bool>> = OnceLock::new();
+
+/// The daemon's in-memory state, guarded by the first element of `Share`.
+#[derive(Debug, Clone, Default)]
+pub struct Latest {
+    pub active: Active,
+    pub simple_config: Config,
+    pub gui_config: GuiConfig,
+    pub hypr_data: HyprlandData,
+    pub launcher: Launcher,
+    /// When the last non-throttled dispatch was applied, used to rate-limit fast key-repeat.
+    pub last_dispatch_at: Option<Instant>,
+    /// The snapshot the GUI renders from; computed from `hypr_data` in `init`.
+    pub overview: Overview,
 }
 
-pub type MonitorId = i64;
\ No newline at end of file
+/// Shared handle between the daemon's IPC thread and the GUI thread: the state mutex plus the
+/// refresh/ack channel pair used by `send_blocking`/`recv_blocking`.
+pub type Share = Arc<(
+    Mutex<Latest>,
+    async_channel::Sender<(GUISend, UpdateCause)>,
+    async_channel::Receiver<()>,
+)>;
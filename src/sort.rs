@@ -0,0 +1,146 @@
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+
+use hyprland::shared::Address;
+
+use crate::cli::SimpleConf;
+use crate::handle::ClientData;
+
+/// Transient most-recently-focused ordering, cleared whenever the GUI closes
+/// (see `daemon::handle_fns::close` / `clear_recent_clients`).
+static RECENT_CLIENTS: OnceLock<Mutex<VecDeque<Address>>> = OnceLock::new();
+
+fn recent_clients() -> &'static Mutex<VecDeque<Address>> {
+    RECENT_CLIENTS.get_or_init(|| Mutex::new(VecDeque::new()))
+}
+
+/// Record that `address` was just focused, moving it to the front of the MRU list.
+pub fn push_recent(address: Address) {
+    let mut recent = recent_clients().lock().expect("Failed to lock recent clients");
+    recent.retain(|a| a != &address);
+    recent.push_front(address);
+}
+
+/// Drop `address` from the MRU list, e.g. once its window has closed.
+pub fn drop_recent(address: &Address) {
+    recent_clients()
+        .lock()
+        .expect("Failed to lock recent clients")
+        .retain(|a| a != address);
+}
+
+/// Clear the whole MRU list; called when the GUI closes.
+pub fn clear_recent_clients() {
+    recent_clients()
+        .lock()
+        .expect("Failed to lock recent clients")
+        .clear();
+}
+
+/// Reorder `clients` in place, most-recently-used first. Clients never seen keep their
+/// relative order at the end.
+pub fn sort_recent(clients: &mut [ClientData]) {
+    let recent = recent_clients().lock().expect("Failed to lock recent clients");
+    clients.sort_by_key(|c| {
+        recent
+            .iter()
+            .position(|a| a == &c.address)
+            .unwrap_or(usize::MAX)
+    });
+}
+
+/// The next most-recently-used client, skipping whichever one is currently active.
+pub fn most_recently_used<'a>(
+    clients: &'a [ClientData],
+    active: &crate::Active,
+) -> Option<&'a ClientData> {
+    let recent = recent_clients().lock().expect("Failed to lock recent clients");
+    let active_address = match active {
+        crate::Active::Client(a) => Some(a),
+        _ => None,
+    };
+    recent
+        .iter()
+        .filter(|a| Some(*a) != active_address)
+        .find_map(|a| clients.iter().find(|c| &c.address == a))
+        .or_else(|| {
+            clients
+                .iter()
+                .filter(|c| Some(&c.address) != active_address)
+                .min_by_key(|c| c.focus_history_id)
+        })
+}
+
+/// Apply `config`'s boolean and title/class regex filters to the switch candidate set, then
+/// order it by recency if `sort_recent` is set.
+pub fn filter(clients: &mut Vec<ClientData>, config: &SimpleConf, active_client: Option<&ClientData>) {
+    if config.filter_same_class {
+        if let Some(active_client) = active_client {
+            clients.retain(|c| c.class == active_client.class);
+        }
+    }
+    if config.filter_current_workspace {
+        if let Some(active_client) = active_client {
+            clients.retain(|c| c.workspace == active_client.workspace);
+        }
+    }
+    if config.filter_current_monitor {
+        if let Some(active_client) = active_client {
+            clients.retain(|c| c.monitor == active_client.monitor);
+        }
+    }
+    if let Some(match_title) = &config.match_title {
+        clients.retain(|c| match_title.0.is_match(&c.title));
+    }
+    if let Some(match_class) = &config.match_class {
+        clients.retain(|c| match_class.0.is_match(&c.class));
+    }
+    if config.sort_recent {
+        sort_recent(clients);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::MatchRegex;
+    use std::str::FromStr;
+
+    fn client(title: &str, class: &str) -> ClientData {
+        ClientData {
+            address: Address::new(format!("0x{title}")),
+            title: title.to_string(),
+            class: class.to_string(),
+            workspace: 1,
+            monitor: 0,
+            at: (0, 0),
+            size: (0, 0),
+            focus_history_id: 0,
+            urgent: false,
+        }
+    }
+
+    #[test]
+    fn match_title_keeps_only_matching_titles() {
+        let mut clients = vec![client("Firefox - Issue 42", "firefox"), client("Terminal", "kitty")];
+        let mut config = SimpleConf::default();
+        config.match_title = Some(MatchRegex::from_str("^Firefox").expect("valid regex"));
+
+        filter(&mut clients, &config, None);
+
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].title, "Firefox - Issue 42");
+    }
+
+    #[test]
+    fn match_class_keeps_only_matching_classes() {
+        let mut clients = vec![client("a", "firefox"), client("b", "kitty")];
+        let mut config = SimpleConf::default();
+        config.match_class = Some(MatchRegex::from_str("kitty|alacritty").expect("valid regex"));
+
+        filter(&mut clients, &config, None);
+
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].class, "kitty");
+    }
+}